@@ -0,0 +1,38 @@
+// Errors the commands raise themselves, so an account owned by a program with no
+// known parser, or a malformed account, can be told apart in the message `main`
+// prints on exit. A missing account or other RPC failure is returned as the RPC
+// client's own error, boxed into the same `CommandResult`.
+
+use solana_sdk::pubkey::Pubkey;
+use std::fmt;
+
+pub type CommandResult = Result<(), Box<dyn std::error::Error>>;
+
+#[derive(Debug)]
+pub enum ObserverError {
+    Deserialization {
+        address: Pubkey,
+        reason: String,
+    },
+    NoParserForOwner {
+        address: Pubkey,
+        owner: Pubkey,
+    },
+}
+
+impl fmt::Display for ObserverError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ObserverError::Deserialization { address, reason } => {
+                write!(f, "failed to decode account {}: {}", address, reason)
+            }
+            ObserverError::NoParserForOwner { address, owner } => write!(
+                f,
+                "no known parser for account {}, owned by program {}",
+                address, owner
+            ),
+        }
+    }
+}
+
+impl std::error::Error for ObserverError {}
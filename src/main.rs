@@ -3,86 +3,506 @@ use solana_clap_utils::input_validators::{
     is_url_or_moniker, is_valid_pubkey, normalize_to_url_if_moniker,
 };
 use solana_client::rpc_client::RpcClient;
+use solana_client::rpc_request::TokenAccountsFilter;
+use solana_program::program_option::COption;
 use solana_program::program_pack::Pack;
 use solana_sdk::pubkey::Pubkey;
 use spl_token::state::{Account, Mint};
 use spl_token_metadata::{
     state::{
-        Key, MasterEditionV2, Metadata, EDITION, MAX_MASTER_EDITION_LEN, MAX_METADATA_LEN, PREFIX,
+        Key, MasterEditionV2, Metadata, MAX_MASTER_EDITION_LEN, MAX_METADATA_LEN,
     },
     utils::try_from_slice_checked,
 };
+use std::collections::HashMap;
 use std::str::FromStr;
 
+mod error;
+mod offchain;
+mod output;
+mod pnft;
+mod token2022;
+
+use error::{CommandResult, ObserverError};
+use output::OutputFormat;
+use std::process;
+
 // Helper functions
 
-fn show_mint(client: RpcClient, address: Pubkey) {
-    let acc_data = client.get_account_data(&address).unwrap();
-    let mint_data = Mint::unpack(acc_data.as_slice()).unwrap();
-    println!("{:?}", mint_data);
+fn token_2022_program_id() -> Pubkey {
+    Pubkey::from_str("TokenzQdBNbLqP5VEhdkAS6EPFLC1PHnBqCXEpPxuEb").unwrap()
 }
 
-fn show_account(client: RpcClient, address: Pubkey) {
-    let acc_data = client.get_account_data(&address).unwrap();
-    let account_data = Account::unpack(acc_data.as_slice()).unwrap();
-    println!("{:?}", account_data);
+fn show_mint(
+    client: RpcClient,
+    address: Pubkey,
+    force_2022: bool,
+    format: OutputFormat,
+) -> CommandResult {
+    let account = client.get_account(&address)?;
+    if force_2022 || account.owner == token_2022_program_id() {
+        token2022::show_mint(&address, &account.data, format)
+    } else {
+        let mint_data = Mint::unpack(account.data.as_slice()).map_err(|err| {
+            ObserverError::Deserialization {
+                address,
+                reason: format!("not an SPL Token mint ({})", err),
+            }
+        })?;
+        output::print_value_or_debug(&mint_data, output::mint_json(&mint_data), format);
+        Ok(())
+    }
 }
 
-fn show_metadata(client: RpcClient, address: Pubkey) {
-    let acc_data = client.get_account_data(&address).unwrap();
-    let account_data: Metadata =
-        try_from_slice_checked(acc_data.as_slice(), Key::MetadataV1, MAX_METADATA_LEN).unwrap();
-    println!("{:?}", account_data);
+fn show_account(
+    client: RpcClient,
+    address: Pubkey,
+    force_2022: bool,
+    format: OutputFormat,
+) -> CommandResult {
+    let account = client.get_account(&address)?;
+    if force_2022 || account.owner == token_2022_program_id() {
+        token2022::show_account(&address, &account.data, format)
+    } else {
+        let account_data = Account::unpack(account.data.as_slice()).map_err(|err| {
+            ObserverError::Deserialization {
+                address,
+                reason: format!("not an SPL Token account ({})", err),
+            }
+        })?;
+        output::print_value_or_debug(&account_data, output::account_json(&account_data), format);
+        Ok(())
+    }
 }
 
-fn show_master_edition(client: RpcClient, address: Pubkey) {
-    let acc_data = client.get_account_data(&address).unwrap();
-    let account_data: MasterEditionV2 = try_from_slice_checked(
-        acc_data.as_slice(),
-        Key::MasterEditionV2,
-        MAX_MASTER_EDITION_LEN,
-    )
-    .unwrap();
-    println!("{:?}", account_data);
-}
-
-fn show_nft(client: RpcClient, mint_address: Pubkey) {
-    let acc_data = client.get_account_data(&mint_address).unwrap();
-    let mint_data = Mint::unpack(acc_data.as_slice()).unwrap();
-    println!("{:?}", mint_data);
-
-    // pda of ['metadata', program id, mint, 'edition']
-    let me_key_seeds = &[
-        PREFIX.as_bytes(),
-        &spl_token_metadata::ID.as_ref(),
-        &mint_address.as_ref(),
-        EDITION.as_bytes(),
-    ];
-    let me_key = Pubkey::find_program_address(me_key_seeds, &spl_token_metadata::ID).0;
-    let acc_data = client.get_account_data(&me_key).unwrap();
+fn show_metadata(
+    client: RpcClient,
+    address: Pubkey,
+    format: OutputFormat,
+    fetch_uri: bool,
+    ipfs_gateway: &str,
+    arweave_gateway: &str,
+) -> CommandResult {
+    let acc_data = client.get_account_data(&address)?;
+    let account_data: Metadata = try_from_slice_checked(acc_data.as_slice(), Key::MetadataV1, MAX_METADATA_LEN)
+        .map_err(|err| ObserverError::Deserialization {
+            address,
+            reason: format!("not a Token Metadata metadata account ({})", err),
+        })?;
+    output::print_value_or_debug(&account_data, output::metadata_json(&account_data), format);
+    if fetch_uri {
+        offchain::fetch_and_print(&account_data.data.uri, ipfs_gateway, arweave_gateway);
+    }
+    Ok(())
+}
+
+fn show_master_edition(client: RpcClient, address: Pubkey, format: OutputFormat) -> CommandResult {
+    let acc_data = client.get_account_data(&address)?;
     let account_data: MasterEditionV2 = try_from_slice_checked(
         acc_data.as_slice(),
         Key::MasterEditionV2,
         MAX_MASTER_EDITION_LEN,
     )
-    .unwrap();
-    println!("{:?}", account_data);
+    .map_err(|err| ObserverError::Deserialization {
+        address,
+        reason: format!("not a Token Metadata master edition account ({})", err),
+    })?;
+    output::print_value_or_debug(
+        &account_data,
+        output::master_edition_json(&account_data),
+        format,
+    );
+    Ok(())
+}
+
+pub(crate) struct Holding {
+    pub(crate) account_address: Pubkey,
+    pub(crate) mint: Pubkey,
+    pub(crate) amount: u64,
+    pub(crate) decimals: u8,
+}
+
+fn show_holdings(
+    client: RpcClient,
+    owner: Pubkey,
+    mint: Option<Pubkey>,
+    nfts_only: bool,
+    format: OutputFormat,
+) -> CommandResult {
+    let filter = match mint {
+        Some(mint) => TokenAccountsFilter::Mint(mint),
+        None => TokenAccountsFilter::ProgramId(spl_token::ID),
+    };
+    let keyed_accounts = client.get_token_accounts_by_owner(&owner, filter)?;
+
+    let mut decimals_by_mint: HashMap<Pubkey, u8> = HashMap::new();
+    let mut holdings = Vec::new();
+    for keyed_account in keyed_accounts {
+        let account_address = Pubkey::from_str(&keyed_account.pubkey)?;
+        let data = keyed_account
+            .account
+            .data
+            .decode()
+            .ok_or_else(|| ObserverError::Deserialization {
+                address: account_address,
+                reason: "token account data could not be decoded".to_owned(),
+            })?;
+        let token_account = Account::unpack(data.as_slice()).map_err(|err| {
+            ObserverError::Deserialization {
+                address: account_address,
+                reason: format!("not an SPL Token account ({})", err),
+            }
+        })?;
+
+        let decimals = match decimals_by_mint.get(&token_account.mint) {
+            Some(decimals) => *decimals,
+            None => {
+                let mint_data = client.get_account_data(&token_account.mint)?;
+                let decimals = Mint::unpack(mint_data.as_slice())
+                    .map_err(|err| ObserverError::Deserialization {
+                        address: token_account.mint,
+                        reason: format!("not an SPL Token mint ({})", err),
+                    })?
+                    .decimals;
+                decimals_by_mint.insert(token_account.mint, decimals);
+                decimals
+            }
+        };
+
+        if nfts_only && !(token_account.amount == 1 && decimals == 0) {
+            continue;
+        }
+
+        holdings.push(Holding {
+            account_address,
+            mint: token_account.mint,
+            amount: token_account.amount,
+            decimals,
+        });
+    }
+
+    let non_zero_count = holdings.iter().filter(|h| h.amount > 0).count();
+
+    match format {
+        OutputFormat::Text => {
+            for holding in &holdings {
+                println!(
+                    "{}: mint={} amount={} decimals={}",
+                    holding.account_address, holding.mint, holding.amount, holding.decimals
+                );
+            }
+            println!(
+                "{} token account(s), {} with a non-zero balance",
+                holdings.len(),
+                non_zero_count
+            );
+        }
+        OutputFormat::Json => println!("{}", output::holdings_json(&holdings)),
+    }
+    Ok(())
+}
+
+// Static dispatch table: owning program -> parser for the account data it owns.
+fn parser_for_program(owner: &Pubkey) -> Option<fn(&Pubkey, &[u8], OutputFormat) -> CommandResult> {
+    if *owner == spl_token::ID {
+        Some(parse_token_program_account)
+    } else if *owner == token_2022_program_id() {
+        Some(parse_token_2022_program_account)
+    } else if *owner == spl_token_metadata::ID {
+        Some(parse_token_metadata_program_account)
+    } else {
+        None
+    }
+}
+
+fn parse_token_2022_program_account(
+    address: &Pubkey,
+    data: &[u8],
+    format: OutputFormat,
+) -> CommandResult {
+    // Extension accounts carry an AccountType byte at `Account::LEN` regardless of
+    // whether the base state is a Mint or an Account: Token-2022 always pads the base
+    // state to `Account::LEN` before that byte (Mint = 1, Account = 2), since with
+    // extensions the total length alone can no longer disambiguate the two.
+    const ACCOUNT_TYPE_MINT: u8 = 1;
+    const ACCOUNT_TYPE_ACCOUNT: u8 = 2;
+    if data.len() == Mint::LEN
+        || (data.len() > Account::LEN && data[Account::LEN] == ACCOUNT_TYPE_MINT)
+    {
+        token2022::show_mint(address, data, format)
+    } else if data.len() == Account::LEN
+        || (data.len() > Account::LEN && data[Account::LEN] == ACCOUNT_TYPE_ACCOUNT)
+    {
+        token2022::show_account(address, data, format)
+    } else {
+        Err(Box::new(ObserverError::Deserialization {
+            address: *address,
+            reason: format!("unrecognized Token-2022 account layout ({} bytes)", data.len()),
+        }))
+    }
+}
+
+fn parse_token_program_account(
+    address: &Pubkey,
+    data: &[u8],
+    format: OutputFormat,
+) -> CommandResult {
+    match data.len() {
+        Mint::LEN => {
+            let mint_data =
+                Mint::unpack(data).map_err(|err| ObserverError::Deserialization {
+                    address: *address,
+                    reason: format!("not an SPL Token mint ({})", err),
+                })?;
+            output::print_value_or_debug(&mint_data, output::mint_json(&mint_data), format);
+        }
+        Account::LEN => {
+            let account_data =
+                Account::unpack(data).map_err(|err| ObserverError::Deserialization {
+                    address: *address,
+                    reason: format!("not an SPL Token account ({})", err),
+                })?;
+            output::print_value_or_debug(&account_data, output::account_json(&account_data), format);
+        }
+        len => {
+            return Err(Box::new(ObserverError::Deserialization {
+                address: *address,
+                reason: format!("unrecognized SPL Token account length: {}", len),
+            }))
+        }
+    }
+    Ok(())
+}
+
+fn parse_token_metadata_program_account(
+    address: &Pubkey,
+    data: &[u8],
+    format: OutputFormat,
+) -> CommandResult {
+    match data.first() {
+        Some(key) if *key == Key::MetadataV1 as u8 => {
+            let metadata: Metadata = try_from_slice_checked(data, Key::MetadataV1, MAX_METADATA_LEN)
+                .map_err(|err| ObserverError::Deserialization {
+                    address: *address,
+                    reason: format!("not a Token Metadata metadata account ({})", err),
+                })?;
+            output::print_value_or_debug(&metadata, output::metadata_json(&metadata), format);
+        }
+        Some(key) if *key == Key::MasterEditionV2 as u8 => {
+            let master_edition: MasterEditionV2 =
+                try_from_slice_checked(data, Key::MasterEditionV2, MAX_MASTER_EDITION_LEN)
+                    .map_err(|err| ObserverError::Deserialization {
+                        address: *address,
+                        reason: format!("not a Token Metadata master edition account ({})", err),
+                    })?;
+            output::print_value_or_debug(
+                &master_edition,
+                output::master_edition_json(&master_edition),
+                format,
+            );
+        }
+        Some(key) => {
+            return Err(Box::new(ObserverError::Deserialization {
+                address: *address,
+                reason: format!("unrecognized Token Metadata account discriminator: {}", key),
+            }))
+        }
+        None => {
+            return Err(Box::new(ObserverError::Deserialization {
+                address: *address,
+                reason: "account data is empty".to_owned(),
+            }))
+        }
+    }
+    Ok(())
+}
+
+fn inspect(client: RpcClient, address: Pubkey, format: OutputFormat) -> CommandResult {
+    let account = client.get_account(&address)?;
+    match parser_for_program(&account.owner) {
+        Some(parser) => parser(&address, &account.data, format),
+        None => Err(Box::new(ObserverError::NoParserForOwner {
+            address,
+            owner: account.owner,
+        })),
+    }
+}
+
+fn show_nft(
+    client: RpcClient,
+    mint_address: Pubkey,
+    format: OutputFormat,
+    fetch_uri: bool,
+    ipfs_gateway: &str,
+    arweave_gateway: &str,
+    token_account: Option<Pubkey>,
+) -> CommandResult {
+    let acc_data = client.get_account_data(&mint_address)?;
+    let mint_data = Mint::unpack(acc_data.as_slice()).map_err(|err| ObserverError::Deserialization {
+        address: mint_address,
+        reason: format!("not an SPL Token mint ({})", err),
+    })?;
+
+    let meta_key = pnft::derive_metadata_pda(&mint_address);
+    let acc_data = client.get_account_data(&meta_key)?;
+    let metadata: Metadata = try_from_slice_checked(acc_data.as_slice(), Key::MetadataV1, MAX_METADATA_LEN)
+        .map_err(|err| ObserverError::Deserialization {
+            address: meta_key,
+            reason: format!("not a Token Metadata metadata account ({})", err),
+        })?;
+
+    let token_standard = pnft::read_token_standard(acc_data.as_slice(), &metadata);
+    println!(
+        "Token standard: {:?} (assuming legacy NonFungible if unset)",
+        token_standard
+    );
+
+    let master_edition_data = if token_standard.map_or(true, pnft::TokenStandard::has_master_edition)
+    {
+        let me_key = pnft::derive_master_edition_pda(&mint_address);
+        let acc_data = client.get_account_data(&me_key)?;
+        Some(
+            try_from_slice_checked::<MasterEditionV2>(
+                acc_data.as_slice(),
+                Key::MasterEditionV2,
+                MAX_MASTER_EDITION_LEN,
+            )
+            .map_err(|err| ObserverError::Deserialization {
+                address: me_key,
+                reason: format!("not a Token Metadata master edition account ({})", err),
+            })?,
+        )
+    } else {
+        println!(
+            "Skipping master edition lookup: {:?} mints don't have one",
+            token_standard
+        );
+        None
+    };
+
+    match format {
+        OutputFormat::Text => {
+            println!("{:?}", mint_data);
+            println!("{:?}", metadata);
+            if let Some(master_edition_data) = &master_edition_data {
+                println!("{:?}", master_edition_data);
+            }
+        }
+        OutputFormat::Json => {
+            let nft = serde_json::json!({
+                "mint": output::mint_json(&mint_data),
+                "metadata": output::metadata_json(&metadata),
+                "masterEdition": master_edition_data.as_ref().map(output::master_edition_json),
+            });
+            println!("{}", nft);
+        }
+    }
+
+    if token_standard == Some(pnft::TokenStandard::ProgrammableNonFungible) {
+        match token_account {
+            Some(token_account) => {
+                let token_record_key =
+                    pnft::derive_token_record_pda(&mint_address, &token_account);
+                let acc_data = client.get_account_data(&token_record_key)?;
+                let token_record = pnft::parse_token_record(acc_data.as_slice(), &token_record_key)?;
+                println!("Token record: {:?}", token_record);
+            }
+            None => println!(
+                "This is a Programmable NFT: pass --token-account to inspect its TokenRecord"
+            ),
+        }
+    }
+
+    if fetch_uri {
+        offchain::fetch_and_print(&metadata.data.uri, ipfs_gateway, arweave_gateway);
+    }
+    Ok(())
+}
+
+struct Check {
+    name: &'static str,
+    passed: bool,
+    detail: String,
+}
+
+// Re-derives the PDAs `show_nft` relies on and asserts the invariants Metaplex
+// enforces for a legitimately-structured (non-programmable) NFT, reporting each
+// check as pass/fail instead of panicking on the first missing or malformed account.
+fn verify(client: RpcClient, mint_address: Pubkey) -> CommandResult {
+    let mut checks = Vec::new();
+
+    let meta_key = pnft::derive_metadata_pda(&mint_address);
+    let metadata_account = client.get_account(&meta_key).ok();
+    checks.push(Check {
+        name: "metadata account exists and is owned by the Token Metadata program",
+        passed: metadata_account
+            .as_ref()
+            .map_or(false, |account| account.owner == spl_token_metadata::ID),
+        detail: format!("metadata PDA {}", meta_key),
+    });
 
-    // pda of ['metadata', program id, mint id]
-    let meta_key_seeds = &[
-        PREFIX.as_bytes(),
-        &spl_token_metadata::ID.as_ref(),
-        &mint_address.as_ref(),
-    ];
-    let meta_key = Pubkey::find_program_address(meta_key_seeds, &spl_token_metadata::ID).0;
+    let me_key = pnft::derive_master_edition_pda(&mint_address);
+    let master_edition_account = client.get_account(&me_key).ok();
+    checks.push(Check {
+        name: "master edition account exists and is owned by the Token Metadata program",
+        passed: master_edition_account
+            .as_ref()
+            .map_or(false, |account| account.owner == spl_token_metadata::ID),
+        detail: format!("master edition PDA {}", me_key),
+    });
 
-    let acc_data = client.get_account_data(&meta_key).unwrap();
-    let meta_data: Metadata =
-        try_from_slice_checked(acc_data.as_slice(), Key::MetadataV1, MAX_METADATA_LEN).unwrap();
-    println!("{:?}", meta_data);
+    let mint_data = client
+        .get_account_data(&mint_address)
+        .ok()
+        .and_then(|data| Mint::unpack(data.as_slice()).ok());
+
+    checks.push(Check {
+        name: "mint authority is the master edition PDA",
+        passed: mint_data
+            .as_ref()
+            .map_or(false, |mint| mint.mint_authority == COption::Some(me_key)),
+        detail: match &mint_data {
+            Some(mint) => format!("mint_authority = {:?}", mint.mint_authority),
+            None => "mint account could not be decoded".to_owned(),
+        },
+    });
+
+    checks.push(Check {
+        name: "supply == 1 and decimals == 0",
+        passed: mint_data
+            .as_ref()
+            .map_or(false, |mint| mint.supply == 1 && mint.decimals == 0),
+        detail: match &mint_data {
+            Some(mint) => format!("supply = {}, decimals = {}", mint.supply, mint.decimals),
+            None => "mint account could not be decoded".to_owned(),
+        },
+    });
+
+    let all_passed = checks.iter().all(|check| check.passed);
+    for check in &checks {
+        let status = if check.passed { "PASS" } else { "FAIL" };
+        println!("[{}] {} ({})", status, check.name, check.detail);
+    }
+    println!(
+        "{}: {}",
+        mint_address,
+        if all_passed {
+            "looks like a legitimately-structured NFT"
+        } else {
+            "failed one or more checks, treat with suspicion"
+        }
+    );
+    Ok(())
 }
 
 fn main() {
+    if let Err(err) = run() {
+        eprintln!("Error: {}", err);
+        process::exit(1);
+    }
+}
+
+fn run() -> CommandResult {
     let app_matches = App::new(crate_name!())
         .about(crate_description!())
         .version(crate_version!())
@@ -100,6 +520,16 @@ fn main() {
                     Default is devnet",
                 ),
         )
+        .arg(
+            Arg::with_name("output")
+                .long("output")
+                .value_name("FORMAT")
+                .takes_value(true)
+                .global(true)
+                .possible_values(&["text", "json"])
+                .default_value("text")
+                .help("Output format"),
+        )
         .subcommand(
             SubCommand::with_name("mint")
                 .arg(
@@ -109,6 +539,11 @@ fn main() {
                         .takes_value(true)
                         .help("Address"),
                 )
+                .arg(
+                    Arg::with_name("program_2022")
+                        .long("program-2022")
+                        .help("Force decoding as a Token-2022 mint instead of detecting it from the account owner"),
+                )
                 .about("Show Token Mint"),
         )
         .subcommand(
@@ -120,6 +555,11 @@ fn main() {
                         .takes_value(true)
                         .help("Address"),
                 )
+                .arg(
+                    Arg::with_name("program_2022")
+                        .long("program-2022")
+                        .help("Force decoding as a Token-2022 account instead of detecting it from the account owner"),
+                )
                 .about("Show Token account"),
         )
         .subcommand(
@@ -131,6 +571,11 @@ fn main() {
                         .takes_value(true)
                         .help("Address"),
                 )
+                .arg(
+                    Arg::with_name("fetch_uri")
+                        .long("fetch-uri")
+                        .help("Resolve and print the off-chain JSON metadata pointed to by the on-chain uri"),
+                )
                 .about("Show Token metaplex metadata account"),
         )
         .subcommand(
@@ -144,6 +589,41 @@ fn main() {
                 )
                 .about("Show Token metaplex master edition account"),
         )
+        .subcommand(
+            SubCommand::with_name("inspect")
+                .arg(
+                    Arg::with_name("address")
+                        .validator(is_valid_pubkey)
+                        .value_name("PUBKEY")
+                        .takes_value(true)
+                        .help("Address"),
+                )
+                .about("Fetch an account and auto-detect its type from the owning program"),
+        )
+        .subcommand(
+            SubCommand::with_name("holdings")
+                .arg(
+                    Arg::with_name("address")
+                        .validator(is_valid_pubkey)
+                        .value_name("OWNER_PUBKEY")
+                        .takes_value(true)
+                        .help("Owner address"),
+                )
+                .arg(
+                    Arg::with_name("mint")
+                        .long("mint")
+                        .validator(is_valid_pubkey)
+                        .value_name("MINT_PUBKEY")
+                        .takes_value(true)
+                        .help("Only list token accounts for this mint"),
+                )
+                .arg(
+                    Arg::with_name("nfts_only")
+                        .long("nfts-only")
+                        .help("Only list accounts with amount=1 and decimals=0"),
+                )
+                .about("List all token accounts owned by a wallet"),
+        )
         .subcommand(
             SubCommand::with_name("nft")
                 .arg(
@@ -153,8 +633,50 @@ fn main() {
                         .takes_value(true)
                         .help("Address"),
                 )
+                .arg(
+                    Arg::with_name("fetch_uri")
+                        .long("fetch-uri")
+                        .help("Resolve and print the off-chain JSON metadata pointed to by the on-chain uri"),
+                )
+                .arg(
+                    Arg::with_name("token_account")
+                        .long("token-account")
+                        .validator(is_valid_pubkey)
+                        .value_name("PUBKEY")
+                        .takes_value(true)
+                        .help("Token account holding this NFT, used to look up its TokenRecord if it's a Programmable NFT"),
+                )
                 .about("Show full NFT accounts pack"),
         )
+        .subcommand(
+            SubCommand::with_name("verify")
+                .arg(
+                    Arg::with_name("address")
+                        .validator(is_valid_pubkey)
+                        .value_name("MINT_PUBKEY")
+                        .takes_value(true)
+                        .help("Mint address"),
+                )
+                .about("Validate the metadata/master-edition PDA derivations and mint invariants for an NFT"),
+        )
+        .arg(
+            Arg::with_name("ipfs_gateway")
+                .long("ipfs-gateway")
+                .value_name("URL")
+                .takes_value(true)
+                .global(true)
+                .default_value(offchain::DEFAULT_IPFS_GATEWAY)
+                .help("Gateway used to resolve ipfs:// off-chain metadata uris"),
+        )
+        .arg(
+            Arg::with_name("arweave_gateway")
+                .long("arweave-gateway")
+                .value_name("URL")
+                .takes_value(true)
+                .global(true)
+                .default_value(offchain::DEFAULT_ARWEAVE_GATEWAY)
+                .help("Gateway used to resolve ar:// off-chain metadata uris"),
+        )
         .get_matches();
 
     let json_rpc_url = normalize_to_url_if_moniker(
@@ -162,36 +684,92 @@ fn main() {
             .value_of("json_rpc_url")
             .unwrap_or(&"https://api.devnet.solana.com".to_owned()),
     );
-    println!("RPC Client URL: {}", json_rpc_url);
+    eprintln!("RPC Client URL: {}", json_rpc_url);
     let client = RpcClient::new(json_rpc_url);
 
+    let output_format = OutputFormat::from_str(app_matches.value_of("output").unwrap()).unwrap();
+    let ipfs_gateway = app_matches.value_of("ipfs_gateway").unwrap().to_owned();
+    let arweave_gateway = app_matches.value_of("arweave_gateway").unwrap().to_owned();
+
     let (sub_command, sub_matches) = app_matches.subcommand();
     match (sub_command, sub_matches) {
         ("mint", Some(arg_matches)) => {
             let address = arg_matches.value_of("address").unwrap();
-            println!("Showing mint {}", address);
-            show_mint(client, Pubkey::from_str(address).unwrap());
+            eprintln!("Showing mint {}", address);
+            show_mint(
+                client,
+                Pubkey::from_str(address).unwrap(),
+                arg_matches.is_present("program_2022"),
+                output_format,
+            )?;
         }
         ("account", Some(arg_matches)) => {
             let address = arg_matches.value_of("address").unwrap();
-            println!("Showing account {}", address);
-            show_account(client, Pubkey::from_str(address).unwrap());
+            eprintln!("Showing account {}", address);
+            show_account(
+                client,
+                Pubkey::from_str(address).unwrap(),
+                arg_matches.is_present("program_2022"),
+                output_format,
+            )?;
         }
         ("metadata", Some(arg_matches)) => {
             let address = arg_matches.value_of("address").unwrap();
-            println!("Showing metaplex metadata {}", address);
-            show_metadata(client, Pubkey::from_str(address).unwrap());
+            eprintln!("Showing metaplex metadata {}", address);
+            show_metadata(
+                client,
+                Pubkey::from_str(address).unwrap(),
+                output_format,
+                arg_matches.is_present("fetch_uri"),
+                &ipfs_gateway,
+                &arweave_gateway,
+            )?;
         }
         ("master-edition", Some(arg_matches)) => {
             let address = arg_matches.value_of("address").unwrap();
-            println!("Showing metaplex master edition {}", address);
-            show_master_edition(client, Pubkey::from_str(address).unwrap());
+            eprintln!("Showing metaplex master edition {}", address);
+            show_master_edition(client, Pubkey::from_str(address).unwrap(), output_format)?;
+        }
+        ("inspect", Some(arg_matches)) => {
+            let address = arg_matches.value_of("address").unwrap();
+            eprintln!("Inspecting {}", address);
+            inspect(client, Pubkey::from_str(address).unwrap(), output_format)?;
+        }
+        ("holdings", Some(arg_matches)) => {
+            let address = arg_matches.value_of("address").unwrap();
+            eprintln!("Showing holdings for {}", address);
+            let mint = arg_matches
+                .value_of("mint")
+                .map(|mint| Pubkey::from_str(mint).unwrap());
+            show_holdings(
+                client,
+                Pubkey::from_str(address).unwrap(),
+                mint,
+                arg_matches.is_present("nfts_only"),
+                output_format,
+            )?;
         }
         ("nft", Some(arg_matches)) => {
             let address = arg_matches.value_of("address").unwrap();
-            println!("Showing NFT (by Mint address) {}", address);
-            show_nft(client, Pubkey::from_str(address).unwrap());
+            eprintln!("Showing NFT (by Mint address) {}", address);
+            show_nft(
+                client,
+                Pubkey::from_str(address).unwrap(),
+                output_format,
+                arg_matches.is_present("fetch_uri"),
+                &ipfs_gateway,
+                &arweave_gateway,
+                arg_matches
+                    .value_of("token_account")
+                    .map(|pubkey| Pubkey::from_str(pubkey).unwrap()),
+            )?;
+        }
+        ("verify", Some(arg_matches)) => {
+            let address = arg_matches.value_of("address").unwrap();
+            eprintln!("Verifying NFT {}", address);
+            verify(client, Pubkey::from_str(address).unwrap())?;
         }
         _ => unreachable!(),
     }
+    Ok(())
 }
@@ -0,0 +1,106 @@
+// Resolves the off-chain JSON metadata a Metaplex `Metadata.data.uri` points at,
+// following the standard off-chain schema: https://docs.metaplex.com/programs/token-metadata/token-standard#the-metadata-file
+
+use serde::Deserialize;
+
+pub const DEFAULT_IPFS_GATEWAY: &str = "https://ipfs.io/ipfs/";
+pub const DEFAULT_ARWEAVE_GATEWAY: &str = "https://arweave.net/";
+
+#[derive(Deserialize)]
+pub struct OffChainMetadata {
+    pub name: Option<String>,
+    pub description: Option<String>,
+    pub image: Option<String>,
+    #[serde(default)]
+    pub attributes: Vec<Attribute>,
+    pub properties: Option<Properties>,
+}
+
+#[derive(Deserialize)]
+pub struct Attribute {
+    pub trait_type: Option<String>,
+    pub value: serde_json::Value,
+}
+
+#[derive(Deserialize)]
+pub struct Properties {
+    #[serde(default)]
+    pub files: Vec<FileEntry>,
+}
+
+#[derive(Deserialize)]
+pub struct FileEntry {
+    pub uri: Option<String>,
+    #[serde(rename = "type")]
+    pub file_type: Option<String>,
+}
+
+// Rewrites `ipfs://` and `ar://` URIs to an HTTP(S) gateway; anything else passes through.
+pub fn resolve_uri(uri: &str, ipfs_gateway: &str, arweave_gateway: &str) -> String {
+    if let Some(path) = uri.strip_prefix("ipfs://") {
+        format!("{}{}", ipfs_gateway, path)
+    } else if let Some(path) = uri.strip_prefix("ar://") {
+        format!("{}{}", arweave_gateway, path)
+    } else {
+        uri.to_owned()
+    }
+}
+
+pub fn fetch(
+    uri: &str,
+    ipfs_gateway: &str,
+    arweave_gateway: &str,
+) -> Result<OffChainMetadata, String> {
+    let resolved = resolve_uri(uri, ipfs_gateway, arweave_gateway);
+    reqwest::blocking::get(&resolved)
+        .and_then(|response| response.error_for_status())
+        .map_err(|err| format!("failed to fetch {}: {}", resolved, err))?
+        .json::<OffChainMetadata>()
+        .map_err(|err| format!("failed to parse off-chain metadata from {}: {}", resolved, err))
+}
+
+pub fn print(metadata: &OffChainMetadata, ipfs_gateway: &str, arweave_gateway: &str) {
+    if let Some(name) = &metadata.name {
+        println!("Off-chain name: {}", name);
+    }
+    if let Some(description) = &metadata.description {
+        println!("Off-chain description: {}", description);
+    }
+    if let Some(image) = &metadata.image {
+        println!(
+            "Off-chain image: {}",
+            resolve_uri(image, ipfs_gateway, arweave_gateway)
+        );
+    }
+    if !metadata.attributes.is_empty() {
+        println!("Traits:");
+        for attribute in &metadata.attributes {
+            let trait_type = attribute.trait_type.as_deref().unwrap_or("?");
+            println!("  {}: {}", trait_type, attribute.value);
+        }
+    }
+    if let Some(properties) = &metadata.properties {
+        for file in &properties.files {
+            if let Some(uri) = &file.uri {
+                println!(
+                    "File ({}): {}",
+                    file.file_type.as_deref().unwrap_or("?"),
+                    resolve_uri(uri, ipfs_gateway, arweave_gateway)
+                );
+            }
+        }
+    }
+}
+
+// Fetches and prints off-chain metadata, warning instead of failing the whole command
+// on network errors or malformed JSON.
+pub fn fetch_and_print(uri: &str, ipfs_gateway: &str, arweave_gateway: &str) {
+    let uri = uri.trim_matches(char::from(0)).trim();
+    if uri.is_empty() {
+        return;
+    }
+    match fetch(uri, ipfs_gateway, arweave_gateway) {
+        Ok(metadata) => print(&metadata, ipfs_gateway, arweave_gateway),
+        Err(err) => eprintln!("Warning: {}", err),
+    }
+}
@@ -0,0 +1,137 @@
+// `--output json` support: turn the decoded on-chain structures into
+// `serde_json::Value`s instead of relying on `{:?}` Debug output, mirroring the
+// `UiAccount`/`parse_token` convention used by Solana's own account-decoder crate.
+
+use serde_json::{json, Value};
+use solana_program::program_option::COption;
+use solana_sdk::pubkey::Pubkey;
+use spl_token::state::{Account, Mint};
+use spl_token_metadata::state::{Data, MasterEditionV2, Metadata};
+use std::str::FromStr;
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum OutputFormat {
+    Text,
+    Json,
+}
+
+impl FromStr for OutputFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "text" => Ok(OutputFormat::Text),
+            "json" => Ok(OutputFormat::Json),
+            other => Err(format!("Invalid output format: {}", other)),
+        }
+    }
+}
+
+fn trimmed(s: &str) -> String {
+    s.trim_matches(char::from(0)).trim().to_owned()
+}
+
+fn pubkey_json(pubkey: &Pubkey) -> Value {
+    json!(pubkey.to_string())
+}
+
+fn coption_pubkey_json(option: &COption<Pubkey>) -> Value {
+    match option {
+        COption::Some(pubkey) => pubkey_json(pubkey),
+        COption::None => Value::Null,
+    }
+}
+
+fn coption_u64_json(option: &COption<u64>) -> Value {
+    match option {
+        COption::Some(amount) => json!(amount),
+        COption::None => Value::Null,
+    }
+}
+
+pub fn mint_json(mint: &Mint) -> Value {
+    json!({
+        "mintAuthority": coption_pubkey_json(&mint.mint_authority),
+        "supply": mint.supply,
+        "decimals": mint.decimals,
+        "isInitialized": mint.is_initialized,
+        "freezeAuthority": coption_pubkey_json(&mint.freeze_authority),
+    })
+}
+
+pub fn account_json(account: &Account) -> Value {
+    json!({
+        "mint": pubkey_json(&account.mint),
+        "owner": pubkey_json(&account.owner),
+        "amount": account.amount,
+        "delegate": coption_pubkey_json(&account.delegate),
+        "state": format!("{:?}", account.state),
+        "isNative": coption_u64_json(&account.is_native),
+        "delegatedAmount": account.delegated_amount,
+        "closeAuthority": coption_pubkey_json(&account.close_authority),
+    })
+}
+
+fn data_json(data: &Data) -> Value {
+    json!({
+        "name": trimmed(&data.name),
+        "symbol": trimmed(&data.symbol),
+        "uri": trimmed(&data.uri),
+        "sellerFeeBasisPoints": data.seller_fee_basis_points,
+        "creators": data.creators.as_ref().map(|creators| {
+            creators
+                .iter()
+                .map(|creator| {
+                    json!({
+                        "address": pubkey_json(&creator.address),
+                        "verified": creator.verified,
+                        "share": creator.share,
+                    })
+                })
+                .collect::<Vec<_>>()
+        }),
+    })
+}
+
+pub fn metadata_json(metadata: &Metadata) -> Value {
+    json!({
+        "updateAuthority": pubkey_json(&metadata.update_authority),
+        "mint": pubkey_json(&metadata.mint),
+        "data": data_json(&metadata.data),
+        "primarySaleHappened": metadata.primary_sale_happened,
+        "isMutable": metadata.is_mutable,
+        "editionNonce": metadata.edition_nonce,
+    })
+}
+
+pub fn master_edition_json(master_edition: &MasterEditionV2) -> Value {
+    json!({
+        "supply": master_edition.supply,
+        "maxSupply": master_edition.max_supply,
+    })
+}
+
+pub fn holdings_json(holdings: &[crate::Holding]) -> Value {
+    let non_zero_count = holdings.iter().filter(|holding| holding.amount > 0).count();
+    json!({
+        "accounts": holdings
+            .iter()
+            .map(|holding| {
+                json!({
+                    "account": pubkey_json(&holding.account_address),
+                    "mint": pubkey_json(&holding.mint),
+                    "amount": holding.amount,
+                    "decimals": holding.decimals,
+                })
+            })
+            .collect::<Vec<_>>(),
+        "nonZeroCount": non_zero_count,
+    })
+}
+
+pub fn print_value_or_debug<T: std::fmt::Debug>(value: &T, json_value: Value, format: OutputFormat) {
+    match format {
+        OutputFormat::Text => println!("{:?}", value),
+        OutputFormat::Json => println!("{}", json_value),
+    }
+}
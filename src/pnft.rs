@@ -0,0 +1,234 @@
+// Token-standard awareness for `show_nft`. Older `Metadata` accounts don't carry a
+// `token_standard` field at all; newer ones append it (and, for pNFTs, a TokenRecord
+// PDA exists per token account) after the fields this crate's `Metadata` struct
+// already knows how to deserialize. Rather than forking that struct, walk past the
+// fields it parsed to read the trailing byte(s) the account may or may not have.
+
+use solana_sdk::pubkey::Pubkey;
+use spl_token_metadata::state::{Metadata, EDITION, PREFIX};
+
+use crate::error::ObserverError;
+
+pub const TOKEN_RECORD_SEED: &str = "token_record";
+
+pub fn derive_metadata_pda(mint_address: &Pubkey) -> Pubkey {
+    let seeds = &[
+        PREFIX.as_bytes(),
+        spl_token_metadata::ID.as_ref(),
+        mint_address.as_ref(),
+    ];
+    Pubkey::find_program_address(seeds, &spl_token_metadata::ID).0
+}
+
+pub fn derive_master_edition_pda(mint_address: &Pubkey) -> Pubkey {
+    let seeds = &[
+        PREFIX.as_bytes(),
+        spl_token_metadata::ID.as_ref(),
+        mint_address.as_ref(),
+        EDITION.as_bytes(),
+    ];
+    Pubkey::find_program_address(seeds, &spl_token_metadata::ID).0
+}
+
+pub fn derive_token_record_pda(mint_address: &Pubkey, token_account: &Pubkey) -> Pubkey {
+    let seeds = &[
+        PREFIX.as_bytes(),
+        spl_token_metadata::ID.as_ref(),
+        mint_address.as_ref(),
+        TOKEN_RECORD_SEED.as_bytes(),
+        token_account.as_ref(),
+    ];
+    Pubkey::find_program_address(seeds, &spl_token_metadata::ID).0
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TokenStandard {
+    NonFungible,
+    FungibleAsset,
+    Fungible,
+    NonFungibleEdition,
+    ProgrammableNonFungible,
+}
+
+impl TokenStandard {
+    fn from_u8(value: u8) -> Option<Self> {
+        match value {
+            0 => Some(TokenStandard::NonFungible),
+            1 => Some(TokenStandard::FungibleAsset),
+            2 => Some(TokenStandard::Fungible),
+            3 => Some(TokenStandard::NonFungibleEdition),
+            4 => Some(TokenStandard::ProgrammableNonFungible),
+            _ => None,
+        }
+    }
+
+    // Only these standards mint a MasterEdition account; fetching one for anything
+    // else would either 404 or, worse, deserialize unrelated data.
+    pub fn has_master_edition(self) -> bool {
+        matches!(self, TokenStandard::NonFungible | TokenStandard::ProgrammableNonFungible)
+    }
+}
+
+// Walks past the borsh fields `Metadata` already parsed (which are variable-length,
+// since `data.name`/`symbol`/`uri` and `data.creators` are borsh strings/vecs) to the
+// trailing `token_standard: Option<TokenStandard>` appended by newer program versions.
+// Returns `None` (legacy metadata, no token standard recorded) if the account is too
+// short to contain it.
+pub fn read_token_standard(acc_data: &[u8], metadata: &Metadata) -> Option<TokenStandard> {
+    let mut offset = 1 + 32 + 32; // key + update_authority + mint
+    offset += 4 + metadata.data.name.len();
+    offset += 4 + metadata.data.symbol.len();
+    offset += 4 + metadata.data.uri.len();
+    offset += 2; // seller_fee_basis_points
+
+    offset += 1; // creators: Option discriminant
+    if let Some(creators) = &metadata.data.creators {
+        offset += 4 + creators.len() * (32 + 1 + 1);
+    }
+
+    offset += 1; // primary_sale_happened
+    offset += 1; // is_mutable
+
+    offset += 1; // edition_nonce: Option discriminant
+    if metadata.edition_nonce.is_some() {
+        offset += 1;
+    }
+
+    match acc_data.get(offset) {
+        Some(0) => None,
+        Some(1) => acc_data.get(offset + 1).and_then(|b| TokenStandard::from_u8(*b)),
+        _ => None,
+    }
+}
+
+#[derive(Debug)]
+pub struct TokenRecord {
+    pub state: u8,
+    pub delegate: Option<Pubkey>,
+}
+
+fn truncated(address: &Pubkey) -> ObserverError {
+    ObserverError::Deserialization {
+        address: *address,
+        reason: "token record data is truncated".to_owned(),
+    }
+}
+
+fn read_option_pubkey(
+    data: &[u8],
+    offset: &mut usize,
+    address: &Pubkey,
+) -> Result<Option<Pubkey>, ObserverError> {
+    let has_value = *data.get(*offset).ok_or_else(|| truncated(address))? != 0;
+    *offset += 1;
+    if has_value {
+        let end = *offset + 32;
+        let slice = data.get(*offset..end).ok_or_else(|| truncated(address))?;
+        let pubkey = Pubkey::new(slice);
+        *offset = end;
+        Ok(Some(pubkey))
+    } else {
+        Ok(None)
+    }
+}
+
+// TokenRecord { key: Key, bump: u8, state: TokenState, rule_set_revision: Option<u64>,
+// delegate: Option<Pubkey>, delegate_role: Option<TokenDelegateRole>, locked_transfer: Option<Pubkey> }
+pub fn parse_token_record(data: &[u8], address: &Pubkey) -> Result<TokenRecord, ObserverError> {
+    let mut offset = 1 + 1; // key + bump
+    let state = *data.get(offset).ok_or_else(|| truncated(address))?;
+    offset += 1;
+
+    let has_rule_set_revision = *data.get(offset).ok_or_else(|| truncated(address))? != 0;
+    offset += 1;
+    if has_rule_set_revision {
+        offset += 8;
+    }
+
+    let delegate = read_option_pubkey(data, &mut offset, address)?;
+
+    Ok(TokenRecord { state, delegate })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use spl_token_metadata::state::{Data, Key};
+
+    fn metadata_with(name: &str, symbol: &str, uri: &str) -> Metadata {
+        Metadata {
+            key: Key::MetadataV1,
+            update_authority: Pubkey::new_unique(),
+            mint: Pubkey::new_unique(),
+            data: Data {
+                name: name.to_owned(),
+                symbol: symbol.to_owned(),
+                uri: uri.to_owned(),
+                seller_fee_basis_points: 0,
+                creators: None,
+            },
+            primary_sale_happened: false,
+            is_mutable: true,
+            edition_nonce: None,
+        }
+    }
+
+    fn encode_borsh_string(buf: &mut Vec<u8>, s: &str) {
+        buf.extend_from_slice(&(s.len() as u32).to_le_bytes());
+        buf.extend_from_slice(s.as_bytes());
+    }
+
+    // Hand-encodes the borsh layout `read_token_standard` walks past: key, update_authority,
+    // mint, data.{name,symbol,uri,seller_fee_basis_points,creators}, primary_sale_happened,
+    // is_mutable, edition_nonce, followed by the trailing token_standard byte(s).
+    fn encode_account(metadata: &Metadata, token_standard: Option<u8>) -> Vec<u8> {
+        let mut buf = Vec::new();
+        buf.push(Key::MetadataV1 as u8);
+        buf.extend_from_slice(metadata.update_authority.as_ref());
+        buf.extend_from_slice(metadata.mint.as_ref());
+        encode_borsh_string(&mut buf, &metadata.data.name);
+        encode_borsh_string(&mut buf, &metadata.data.symbol);
+        encode_borsh_string(&mut buf, &metadata.data.uri);
+        buf.extend_from_slice(&metadata.data.seller_fee_basis_points.to_le_bytes());
+        buf.push(0); // creators: None
+        buf.push(if metadata.primary_sale_happened { 1 } else { 0 });
+        buf.push(if metadata.is_mutable { 1 } else { 0 });
+        buf.push(0); // edition_nonce: None
+        match token_standard {
+            Some(value) => {
+                buf.push(1);
+                buf.push(value);
+            }
+            None => buf.push(0),
+        }
+        buf
+    }
+
+    #[test]
+    fn read_token_standard_finds_trailing_byte_past_variable_length_fields() {
+        let metadata = metadata_with("a long name to shift the offset", "SYM", "https://example.com/metadata.json");
+        let data = encode_account(&metadata, Some(4));
+
+        assert_eq!(
+            read_token_standard(&data, &metadata),
+            Some(TokenStandard::ProgrammableNonFungible)
+        );
+    }
+
+    #[test]
+    fn read_token_standard_none_when_legacy_account_has_no_trailing_field() {
+        let metadata = metadata_with("name", "SYM", "uri");
+        let mut data = encode_account(&metadata, None);
+        data.pop(); // legacy account: too short to contain even the Option discriminant
+
+        assert_eq!(read_token_standard(&data, &metadata), None);
+    }
+
+    #[test]
+    fn read_token_standard_none_when_discriminant_is_explicit_none() {
+        let metadata = metadata_with("name", "SYM", "uri");
+        let data = encode_account(&metadata, None);
+
+        assert_eq!(read_token_standard(&data, &metadata), None);
+    }
+}
@@ -0,0 +1,457 @@
+// Support for SPL Token-2022 accounts: a base Mint/Account layout identical to
+// legacy spl_token, followed by an account-type byte and a TLV (type-length-value)
+// region of extensions. Unknown extensions are skipped rather than rejected, since
+// new extension types are added to the program over time.
+
+use serde_json::{json, Value};
+use solana_program::program_pack::Pack;
+use solana_sdk::pubkey::Pubkey;
+use spl_token::state::{Account, Mint};
+
+use crate::error::{CommandResult, ObserverError};
+use crate::output::{self, OutputFormat};
+
+const ACCOUNT_TYPE_LEN: usize = 1;
+const TLV_TYPE_LEN: usize = 2;
+const TLV_LENGTH_LEN: usize = 2;
+
+// A subset of spl_token_2022::extension::ExtensionType, just enough to print the
+// extensions this tool knows how to decode.
+const EXT_TRANSFER_FEE_CONFIG: u16 = 1;
+const EXT_MINT_CLOSE_AUTHORITY: u16 = 3;
+const EXT_INTEREST_BEARING_CONFIG: u16 = 10;
+const EXT_METADATA_POINTER: u16 = 18;
+const EXT_TOKEN_METADATA: u16 = 19;
+
+#[derive(Debug)]
+struct TransferFee {
+    epoch: u64,
+    maximum_fee: u64,
+    transfer_fee_basis_points: u16,
+}
+
+#[derive(Debug)]
+struct TransferFeeConfig {
+    transfer_fee_config_authority: Option<Pubkey>,
+    withdraw_withheld_authority: Option<Pubkey>,
+    withheld_amount: u64,
+    older_transfer_fee: TransferFee,
+    newer_transfer_fee: TransferFee,
+}
+
+#[derive(Debug)]
+struct MintCloseAuthority {
+    close_authority: Option<Pubkey>,
+}
+
+#[derive(Debug)]
+struct InterestBearingConfig {
+    rate_authority: Option<Pubkey>,
+    initialization_timestamp: i64,
+    pre_update_average_rate: i16,
+    last_update_timestamp: i64,
+    current_rate: i16,
+}
+
+#[derive(Debug)]
+struct MetadataPointer {
+    authority: Option<Pubkey>,
+    metadata_address: Option<Pubkey>,
+}
+
+#[derive(Debug)]
+struct TokenMetadata {
+    update_authority: Option<Pubkey>,
+    mint: Pubkey,
+    name: String,
+    symbol: String,
+    uri: String,
+    additional_metadata: Vec<(String, String)>,
+}
+
+fn truncated(address: &Pubkey) -> ObserverError {
+    ObserverError::Deserialization {
+        address: *address,
+        reason: "Token-2022 extension data is truncated".to_owned(),
+    }
+}
+
+fn get_slice<'a>(
+    data: &'a [u8],
+    range: std::ops::Range<usize>,
+    address: &Pubkey,
+) -> Result<&'a [u8], ObserverError> {
+    data.get(range).ok_or_else(|| truncated(address))
+}
+
+fn optional_pubkey(bytes: &[u8]) -> Option<Pubkey> {
+    if bytes.iter().all(|b| *b == 0) {
+        None
+    } else {
+        Some(Pubkey::new(bytes))
+    }
+}
+
+fn pubkey_opt_json(pubkey: &Option<Pubkey>) -> Value {
+    match pubkey {
+        Some(pubkey) => json!(pubkey.to_string()),
+        None => Value::Null,
+    }
+}
+
+fn read_borsh_string(
+    data: &[u8],
+    offset: &mut usize,
+    address: &Pubkey,
+) -> Result<String, ObserverError> {
+    let len_bytes = get_slice(data, *offset..*offset + 4, address)?;
+    let len = u32::from_le_bytes(len_bytes.try_into().unwrap()) as usize;
+    *offset += 4;
+    let bytes = get_slice(data, *offset..*offset + len, address)?;
+    let s = String::from_utf8_lossy(bytes).into_owned();
+    *offset += len;
+    Ok(s)
+}
+
+fn parse_transfer_fee_config(
+    value: &[u8],
+    address: &Pubkey,
+) -> Result<TransferFeeConfig, ObserverError> {
+    let older_transfer_fee = TransferFee {
+        epoch: u64::from_le_bytes(get_slice(value, 72..80, address)?.try_into().unwrap()),
+        maximum_fee: u64::from_le_bytes(get_slice(value, 80..88, address)?.try_into().unwrap()),
+        transfer_fee_basis_points: u16::from_le_bytes(
+            get_slice(value, 88..90, address)?.try_into().unwrap(),
+        ),
+    };
+    let newer_transfer_fee = TransferFee {
+        epoch: u64::from_le_bytes(get_slice(value, 90..98, address)?.try_into().unwrap()),
+        maximum_fee: u64::from_le_bytes(get_slice(value, 98..106, address)?.try_into().unwrap()),
+        transfer_fee_basis_points: u16::from_le_bytes(
+            get_slice(value, 106..108, address)?.try_into().unwrap(),
+        ),
+    };
+    Ok(TransferFeeConfig {
+        transfer_fee_config_authority: optional_pubkey(get_slice(value, 0..32, address)?),
+        withdraw_withheld_authority: optional_pubkey(get_slice(value, 32..64, address)?),
+        withheld_amount: u64::from_le_bytes(get_slice(value, 64..72, address)?.try_into().unwrap()),
+        older_transfer_fee,
+        newer_transfer_fee,
+    })
+}
+
+fn transfer_fee_json(fee: &TransferFee) -> Value {
+    json!({
+        "epoch": fee.epoch,
+        "maximumFee": fee.maximum_fee,
+        "transferFeeBasisPoints": fee.transfer_fee_basis_points,
+    })
+}
+
+fn transfer_fee_config_json(config: &TransferFeeConfig) -> Value {
+    json!({
+        "transferFeeConfigAuthority": pubkey_opt_json(&config.transfer_fee_config_authority),
+        "withdrawWithheldAuthority": pubkey_opt_json(&config.withdraw_withheld_authority),
+        "withheldAmount": config.withheld_amount,
+        "olderTransferFee": transfer_fee_json(&config.older_transfer_fee),
+        "newerTransferFee": transfer_fee_json(&config.newer_transfer_fee),
+    })
+}
+
+fn parse_mint_close_authority(
+    value: &[u8],
+    address: &Pubkey,
+) -> Result<MintCloseAuthority, ObserverError> {
+    Ok(MintCloseAuthority {
+        close_authority: optional_pubkey(get_slice(value, 0..32, address)?),
+    })
+}
+
+fn mint_close_authority_json(config: &MintCloseAuthority) -> Value {
+    json!({ "closeAuthority": pubkey_opt_json(&config.close_authority) })
+}
+
+fn parse_interest_bearing_config(
+    value: &[u8],
+    address: &Pubkey,
+) -> Result<InterestBearingConfig, ObserverError> {
+    Ok(InterestBearingConfig {
+        rate_authority: optional_pubkey(get_slice(value, 0..32, address)?),
+        initialization_timestamp: i64::from_le_bytes(
+            get_slice(value, 32..40, address)?.try_into().unwrap(),
+        ),
+        pre_update_average_rate: i16::from_le_bytes(
+            get_slice(value, 40..42, address)?.try_into().unwrap(),
+        ),
+        last_update_timestamp: i64::from_le_bytes(
+            get_slice(value, 42..50, address)?.try_into().unwrap(),
+        ),
+        current_rate: i16::from_le_bytes(get_slice(value, 50..52, address)?.try_into().unwrap()),
+    })
+}
+
+fn interest_bearing_config_json(config: &InterestBearingConfig) -> Value {
+    json!({
+        "rateAuthority": pubkey_opt_json(&config.rate_authority),
+        "initializationTimestamp": config.initialization_timestamp,
+        "preUpdateAverageRate": config.pre_update_average_rate,
+        "lastUpdateTimestamp": config.last_update_timestamp,
+        "currentRate": config.current_rate,
+    })
+}
+
+fn parse_metadata_pointer(
+    value: &[u8],
+    address: &Pubkey,
+) -> Result<MetadataPointer, ObserverError> {
+    Ok(MetadataPointer {
+        authority: optional_pubkey(get_slice(value, 0..32, address)?),
+        metadata_address: optional_pubkey(get_slice(value, 32..64, address)?),
+    })
+}
+
+fn metadata_pointer_json(pointer: &MetadataPointer) -> Value {
+    json!({
+        "authority": pubkey_opt_json(&pointer.authority),
+        "metadataAddress": pubkey_opt_json(&pointer.metadata_address),
+    })
+}
+
+fn parse_token_metadata(value: &[u8], address: &Pubkey) -> Result<TokenMetadata, ObserverError> {
+    let mut offset = 0;
+    let update_authority = optional_pubkey(get_slice(value, offset..offset + 32, address)?);
+    offset += 32;
+    let mint = Pubkey::new(get_slice(value, offset..offset + 32, address)?);
+    offset += 32;
+    let name = read_borsh_string(value, &mut offset, address)?;
+    let symbol = read_borsh_string(value, &mut offset, address)?;
+    let uri = read_borsh_string(value, &mut offset, address)?;
+    let additional_count = u32::from_le_bytes(
+        get_slice(value, offset..offset + 4, address)?.try_into().unwrap(),
+    ) as usize;
+    offset += 4;
+    let mut additional_metadata = Vec::with_capacity(additional_count);
+    for _ in 0..additional_count {
+        let key = read_borsh_string(value, &mut offset, address)?;
+        let val = read_borsh_string(value, &mut offset, address)?;
+        additional_metadata.push((key, val));
+    }
+    Ok(TokenMetadata {
+        update_authority,
+        mint,
+        name,
+        symbol,
+        uri,
+        additional_metadata,
+    })
+}
+
+fn token_metadata_json(metadata: &TokenMetadata) -> Value {
+    json!({
+        "updateAuthority": pubkey_opt_json(&metadata.update_authority),
+        "mint": metadata.mint.to_string(),
+        "name": metadata.name,
+        "symbol": metadata.symbol,
+        "uri": metadata.uri,
+        "additionalMetadata": metadata.additional_metadata
+            .iter()
+            .map(|(key, val)| json!({ "key": key, "value": val }))
+            .collect::<Vec<_>>(),
+    })
+}
+
+fn print_extension(discriminator: u16, value: &[u8], address: &Pubkey) -> CommandResult {
+    match discriminator {
+        EXT_TRANSFER_FEE_CONFIG => println!("{:?}", parse_transfer_fee_config(value, address)?),
+        EXT_MINT_CLOSE_AUTHORITY => println!("{:?}", parse_mint_close_authority(value, address)?),
+        EXT_INTEREST_BEARING_CONFIG => {
+            println!("{:?}", parse_interest_bearing_config(value, address)?)
+        }
+        EXT_METADATA_POINTER => println!("{:?}", parse_metadata_pointer(value, address)?),
+        EXT_TOKEN_METADATA => println!("{:?}", parse_token_metadata(value, address)?),
+        other => println!(
+            "  (unrecognized extension type {}, {} bytes, skipped)",
+            other,
+            value.len()
+        ),
+    }
+    Ok(())
+}
+
+fn extension_json(discriminator: u16, value: &[u8], address: &Pubkey) -> Result<Value, ObserverError> {
+    let json = match discriminator {
+        EXT_TRANSFER_FEE_CONFIG => {
+            json!({ "transferFeeConfig": transfer_fee_config_json(&parse_transfer_fee_config(value, address)?) })
+        }
+        EXT_MINT_CLOSE_AUTHORITY => {
+            json!({ "mintCloseAuthority": mint_close_authority_json(&parse_mint_close_authority(value, address)?) })
+        }
+        EXT_INTEREST_BEARING_CONFIG => {
+            json!({ "interestBearingConfig": interest_bearing_config_json(&parse_interest_bearing_config(value, address)?) })
+        }
+        EXT_METADATA_POINTER => {
+            json!({ "metadataPointer": metadata_pointer_json(&parse_metadata_pointer(value, address)?) })
+        }
+        EXT_TOKEN_METADATA => {
+            json!({ "tokenMetadata": token_metadata_json(&parse_token_metadata(value, address)?) })
+        }
+        other => json!({ "unrecognized": { "type": other, "lengthBytes": value.len() } }),
+    };
+    Ok(json)
+}
+
+// Walk the TLV region that follows the extensions-enabled layout, returning every
+// `(discriminator, value)` pair it finds. Token-2022 always pads the base state to
+// `Account::LEN` before the account-type byte, even for mints, so the TLV start is
+// fixed regardless of which base state (Mint or Account) actually occupies the front
+// of `data`.
+fn collect_extensions(data: &[u8]) -> Vec<(u16, &[u8])> {
+    let tlv_start = Account::LEN + ACCOUNT_TYPE_LEN;
+    if data.len() <= tlv_start {
+        return Vec::new();
+    }
+    let mut offset = tlv_start;
+    let mut extensions = Vec::new();
+    while offset + TLV_TYPE_LEN + TLV_LENGTH_LEN <= data.len() {
+        let discriminator =
+            u16::from_le_bytes(data[offset..offset + TLV_TYPE_LEN].try_into().unwrap());
+        let length = u16::from_le_bytes(
+            data[offset + TLV_TYPE_LEN..offset + TLV_TYPE_LEN + TLV_LENGTH_LEN]
+                .try_into()
+                .unwrap(),
+        ) as usize;
+        offset += TLV_TYPE_LEN + TLV_LENGTH_LEN;
+        if offset + length > data.len() {
+            break;
+        }
+        extensions.push((discriminator, &data[offset..offset + length]));
+        offset += length;
+    }
+    extensions
+}
+
+fn print_extensions(data: &[u8], address: &Pubkey) -> CommandResult {
+    for (discriminator, value) in collect_extensions(data) {
+        print_extension(discriminator, value, address)?;
+    }
+    Ok(())
+}
+
+fn extensions_json(data: &[u8], address: &Pubkey) -> Result<Vec<Value>, ObserverError> {
+    collect_extensions(data)
+        .into_iter()
+        .map(|(discriminator, value)| extension_json(discriminator, value, address))
+        .collect()
+}
+
+pub fn show_mint(address: &Pubkey, acc_data: &[u8], format: OutputFormat) -> CommandResult {
+    let mint_data =
+        Mint::unpack(&acc_data[..Mint::LEN]).map_err(|err| ObserverError::Deserialization {
+            address: *address,
+            reason: format!("not a Token-2022 mint ({})", err),
+        })?;
+    match format {
+        OutputFormat::Text => {
+            println!("{:?}", mint_data);
+            print_extensions(acc_data, address)?;
+        }
+        OutputFormat::Json => println!(
+            "{}",
+            json!({
+                "mint": output::mint_json(&mint_data),
+                "extensions": extensions_json(acc_data, address)?,
+            })
+        ),
+    }
+    Ok(())
+}
+
+pub fn show_account(address: &Pubkey, acc_data: &[u8], format: OutputFormat) -> CommandResult {
+    let account_data =
+        Account::unpack(&acc_data[..Account::LEN]).map_err(|err| ObserverError::Deserialization {
+            address: *address,
+            reason: format!("not a Token-2022 account ({})", err),
+        })?;
+    match format {
+        OutputFormat::Text => {
+            println!("{:?}", account_data);
+            print_extensions(acc_data, address)?;
+        }
+        OutputFormat::Json => println!(
+            "{}",
+            json!({
+                "account": output::account_json(&account_data),
+                "extensions": extensions_json(acc_data, address)?,
+            })
+        ),
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn base_extension_account(extension_bytes: &[u8]) -> Vec<u8> {
+        let mut data = vec![0u8; Account::LEN];
+        data.push(2); // AccountType::Account
+        data.extend_from_slice(extension_bytes);
+        data
+    }
+
+    #[test]
+    fn collect_extensions_finds_tlv_entries_after_account_type_byte() {
+        let mut transfer_fee_config = vec![0u8; 108];
+        transfer_fee_config[64..72].copy_from_slice(&1_000u64.to_le_bytes());
+        let mut tlv = Vec::new();
+        tlv.extend_from_slice(&EXT_TRANSFER_FEE_CONFIG.to_le_bytes());
+        tlv.extend_from_slice(&(transfer_fee_config.len() as u16).to_le_bytes());
+        tlv.extend_from_slice(&transfer_fee_config);
+
+        let data = base_extension_account(&tlv);
+        let extensions = collect_extensions(&data);
+
+        assert_eq!(extensions.len(), 1);
+        let (discriminator, value) = extensions[0];
+        assert_eq!(discriminator, EXT_TRANSFER_FEE_CONFIG);
+        assert_eq!(value, transfer_fee_config.as_slice());
+    }
+
+    #[test]
+    fn collect_extensions_ignores_trailing_partial_tlv_header() {
+        let data = base_extension_account(&[1, 0]); // 2-byte discriminator only, no length
+        assert!(collect_extensions(&data).is_empty());
+    }
+
+    #[test]
+    fn parse_transfer_fee_config_reads_non_overlapping_fields() {
+        let address = Pubkey::new_unique();
+        let mut value = vec![0u8; 108];
+        value[0..32].copy_from_slice(&[1u8; 32]);
+        value[32..64].copy_from_slice(&[2u8; 32]);
+        value[64..72].copy_from_slice(&42u64.to_le_bytes());
+        value[72..80].copy_from_slice(&7u64.to_le_bytes());
+        value[80..88].copy_from_slice(&100u64.to_le_bytes());
+        value[88..90].copy_from_slice(&50u16.to_le_bytes());
+        value[90..98].copy_from_slice(&8u64.to_le_bytes());
+        value[98..106].copy_from_slice(&200u64.to_le_bytes());
+        value[106..108].copy_from_slice(&75u16.to_le_bytes());
+
+        let config = parse_transfer_fee_config(&value, &address).unwrap();
+
+        assert_eq!(config.withheld_amount, 42);
+        assert_eq!(config.older_transfer_fee.epoch, 7);
+        assert_eq!(config.older_transfer_fee.maximum_fee, 100);
+        assert_eq!(config.older_transfer_fee.transfer_fee_basis_points, 50);
+        assert_eq!(config.newer_transfer_fee.epoch, 8);
+        assert_eq!(config.newer_transfer_fee.maximum_fee, 200);
+        assert_eq!(config.newer_transfer_fee.transfer_fee_basis_points, 75);
+    }
+
+    #[test]
+    fn parse_transfer_fee_config_rejects_truncated_input() {
+        let address = Pubkey::new_unique();
+        let value = vec![0u8; 50]; // shorter than the 108-byte layout
+        assert!(parse_transfer_fee_config(&value, &address).is_err());
+    }
+}